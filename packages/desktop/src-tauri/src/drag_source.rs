@@ -0,0 +1,77 @@
+//! Platform-specific extraction of a dragged URL when the drop event itself
+//! carries no file paths (e.g. a browser tab dragged in from outside the
+//! app). Dispatches to whichever platform backend applies.
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// Picks out the first `http(s)://` URL embedded in `text`, e.g. an
+/// HTML-fragment clipboard payload or a plain-text drag (`text/uri-list`
+/// equivalents on Windows). Shared by backends that read raw clipboard-style
+/// formats off the drag's data object.
+#[cfg(target_os = "windows")]
+pub(crate) fn first_http_url(text: &str) -> Option<String> {
+    // In a CF_HTML payload, skip the header (whose `SourceURL:` names the
+    // page the drag came from) and search the fragment itself.
+    let text = text
+        .find("<!--StartFragment-->")
+        .map_or(text, |i| &text[i..]);
+    let start = [text.find("http://"), text.find("https://")]
+        .into_iter()
+        .flatten()
+        .min()?;
+    let rest = &text[start..];
+    let end = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>'))
+        .unwrap_or(rest.len());
+    Some(rest[..end].to_string())
+}
+
+/// Hooks a platform-specific drag-scoped data source into `window`, where
+/// the drop event itself doesn't carry enough information to recover a URL.
+/// On Windows this replaces wry's registered drop target with one that also
+/// reads the drag's `IDataObject` directly; other platforms have nothing to
+/// set up ahead of time.
+pub fn init_window(window: &tauri::WebviewWindow) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::register(window)?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = window;
+    }
+    Ok(())
+}
+
+/// Attempts to recover the URL behind the drag that just completed, by
+/// reading whatever the source OS staged for the drag itself — not the
+/// general clipboard/selection, which can hold unrelated stale content and
+/// would silently report the wrong URL (or one from days ago). On macOS
+/// this reads the dedicated drag pasteboard. On Windows the real work
+/// happens in the drop target registered by [`init_window`], which emits
+/// the drop events itself; this is only reached as a fallback. Linux isn't
+/// wired up yet (see `drag_source::linux` for why) and reports no URL
+/// rather than guess from the clipboard/selection.
+pub fn get_drag_url() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::get_drag_url()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::get_drag_url()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_drag_url()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
@@ -0,0 +1,23 @@
+//! X11 drag-and-drop (XDND) stages its payload on the `XdndSelection`
+//! selection, which is distinct from `PRIMARY`/`CLIPBOARD` and scoped to the
+//! in-flight drag. Reading it for real means answering the XDND handshake
+//! (`XdndEnter`/`XdndPosition`/`XdndStatus`/`XdndDrop`/`XdndFinished`) on
+//! the portal window and then `XConvertSelection`-ing `XdndSelection` for
+//! `text/uri-list`/`text/plain` — but GTK/WebKitGTK is *already* registered
+//! as that window's sole XDND target (that's how the existing
+//! `DragDropEvent::Drop { paths, .. }` gets its file paths today), and XDND
+//! only expects one target to answer the handshake per window. Answering it
+//! ourselves from a second, independent X connection would race GTK's own
+//! responses and risks breaking the file-drop handling that already works.
+//! Wayland's data-device protocol has the same one-listener shape, via the
+//! compositor rather than the X server.
+//!
+//! Doing this correctly requires the same widget-level access Tauri/wry
+//! uses internally (reaching the WebKitGTK widget's own `drag-drop`/
+//! `drag-data-received` signals to request the extra MIME types alongside
+//! what wry already asks for), which isn't exposed through Tauri's public
+//! window API. Re-scoping this to "not supported" rather than shipping a
+//! parallel XDND responder that could corrupt the existing file-drop path.
+pub fn get_drag_url() -> Option<String> {
+    None
+}
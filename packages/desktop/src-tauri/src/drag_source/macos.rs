@@ -0,0 +1,59 @@
+use cocoa::base::{id, nil};
+use cocoa::foundation::NSString;
+use objc::{class, msg_send, sel, sel_impl};
+
+pub fn get_drag_url() -> Option<String> {
+    unsafe {
+        // Get the drag pasteboard
+        let pasteboard: id = msg_send![class!(NSPasteboard), pasteboardWithName: NSString::alloc(nil).init_str("Apple CFPasteboard drag")];
+
+        if pasteboard == nil {
+            // No drag pasteboard: report nothing rather than read the
+            // general pasteboard, which may hold unrelated content
+            return None;
+        }
+
+        read_url_from_pasteboard(pasteboard)
+    }
+}
+
+unsafe fn read_url_from_pasteboard(pasteboard: id) -> Option<String> {
+    if pasteboard == nil {
+        return None;
+    }
+
+    // Try to read URL type
+    let url_type = NSString::alloc(nil).init_str("public.url");
+    let url_string_type = NSString::alloc(nil).init_str("public.url-name");
+    let string_type = NSString::alloc(nil).init_str("public.utf8-plain-text");
+
+    // Try URL first
+    let content: id = msg_send![pasteboard, stringForType: url_type];
+    if content != nil {
+        let rust_string = nsstring_to_rust(content);
+        if rust_string.starts_with("http://") || rust_string.starts_with("https://") {
+            return Some(rust_string);
+        }
+    }
+
+    // Try plain text (URLs are often stored as text)
+    let content: id = msg_send![pasteboard, stringForType: string_type];
+    if content != nil {
+        let rust_string = nsstring_to_rust(content);
+        if rust_string.starts_with("http://") || rust_string.starts_with("https://") {
+            return Some(rust_string);
+        }
+    }
+
+    None
+}
+
+unsafe fn nsstring_to_rust(nsstring: id) -> String {
+    let cstr: *const i8 = msg_send![nsstring, UTF8String];
+    if cstr.is_null() {
+        return String::new();
+    }
+    std::ffi::CStr::from_ptr(cstr)
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,190 @@
+//! Windows drag-and-drop is delivered through a per-window `IDropTarget`
+//! registered via `RegisterDragDrop`, and a window can only have a *single*
+//! registered drop target at a time. Tauri/wry already registers one (it's
+//! how `DragDropEvent::Drop { paths, .. }` gets its file paths), so to read
+//! the drag's actual `IDataObject` — not a copy mirrored onto the system
+//! clipboard, which an ordinary drag never touches — we replace wry's
+//! registration on the portal window with our own, and re-emit the same
+//! `tauri-drop` / `tauri-drop-url` / `tauri-drag-enter` / `tauri-drag-leave`
+//! events the frontend already listens for so nothing downstream changes.
+
+use std::path::PathBuf;
+
+use tauri::{Emitter, WebviewWindow};
+use windows::core::implement;
+use windows::Win32::Foundation::{HGLOBAL, POINTL};
+use windows::Win32::System::Com::{DVASPECT_CONTENT, FORMATETC, IDataObject, STGMEDIUM, TYMED_HGLOBAL};
+use windows::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+use windows::Win32::System::Ole::{
+    CF_HDROP, CF_UNICODETEXT, IDropTarget, IDropTarget_Impl, OleInitialize, RegisterClipboardFormatW,
+    RegisterDragDrop, RevokeDragDrop,
+};
+use windows::Win32::System::SystemServices::MODIFIERKEYS_FLAGS;
+use windows::Win32::UI::Shell::{DragQueryFileW, HDROP};
+
+use super::first_http_url;
+
+#[implement(IDropTarget)]
+struct PortalDropTarget {
+    window: WebviewWindow,
+}
+
+impl IDropTarget_Impl for PortalDropTarget_Impl {
+    fn DragEnter(
+        &self,
+        data: windows::core::Ref<'_, IDataObject>,
+        _keystate: MODIFIERKEYS_FLAGS,
+        _pt: &POINTL,
+        _effect: *mut windows::Win32::System::Ole::DROPEFFECT,
+    ) -> windows::core::Result<()> {
+        let paths = data.as_ref().map(read_file_paths).unwrap_or_default();
+        let _ = self.window.emit("tauri-drag-enter", &paths);
+        Ok(())
+    }
+
+    fn DragOver(
+        &self,
+        _keystate: MODIFIERKEYS_FLAGS,
+        _pt: &POINTL,
+        _effect: *mut windows::Win32::System::Ole::DROPEFFECT,
+    ) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn DragLeave(&self) -> windows::core::Result<()> {
+        let _ = self.window.emit("tauri-drag-leave", ());
+        Ok(())
+    }
+
+    fn Drop(
+        &self,
+        data: windows::core::Ref<'_, IDataObject>,
+        _keystate: MODIFIERKEYS_FLAGS,
+        _pt: &POINTL,
+        _effect: *mut windows::Win32::System::Ole::DROPEFFECT,
+    ) -> windows::core::Result<()> {
+        let Some(data) = data.as_ref() else {
+            return Ok(());
+        };
+
+        let paths = read_file_paths(data);
+        let url = paths
+            .iter()
+            .find(|p| p.starts_with("http://") || p.starts_with("https://"))
+            .cloned()
+            .or_else(|| read_drag_url(data));
+
+        if let Some(url) = url {
+            let _ = self.window.emit("tauri-drop-url", &url);
+        } else {
+            let _ = self.window.emit("tauri-drop", &paths);
+        }
+
+        Ok(())
+    }
+}
+
+/// Registers our drop target on `window`'s HWND, replacing whatever wry
+/// already registered there. Safe to call every time the portal window is
+/// (re)created.
+pub fn register(window: &WebviewWindow) -> Result<(), String> {
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?;
+    let target: IDropTarget = PortalDropTarget {
+        window: window.clone(),
+    }
+    .into();
+
+    unsafe {
+        // Ignore RPC_E_CHANGED_MODE: WebView2 has already initialized OLE
+        // for this thread by the time we get here.
+        let _ = OleInitialize(None);
+        let _ = RevokeDragDrop(hwnd);
+        RegisterDragDrop(hwnd, &target).map_err(|e| format!("Failed to register portal drop target: {}", e))?;
+    }
+
+    Ok(())
+}
+
+fn read_file_paths(data: &IDataObject) -> Vec<String> {
+    unsafe {
+        let Ok(medium) = data.GetData(&format_etc(CF_HDROP.0)) else {
+            return Vec::new();
+        };
+        if medium.tymed != TYMED_HGLOBAL.0 as u32 {
+            return Vec::new();
+        }
+
+        let hdrop = HDROP(medium.u.hGlobal.0);
+        let count = DragQueryFileW(hdrop, 0xFFFFFFFF, None);
+        let mut paths = Vec::with_capacity(count as usize);
+        for i in 0..count {
+            let mut buf = [0u16; 260];
+            let len = DragQueryFileW(hdrop, i, Some(&mut buf));
+            paths.push(PathBuf::from(String::from_utf16_lossy(&buf[..len as usize])).to_string_lossy().into_owned());
+        }
+        paths
+    }
+}
+
+fn read_drag_url(data: &IDataObject) -> Option<String> {
+    read_html_fragment(data).or_else(|| read_unicode_text(data))
+}
+
+fn read_unicode_text(data: &IDataObject) -> Option<String> {
+    let text = read_format(data, CF_UNICODETEXT.0, true)?;
+    first_http_url(&text)
+}
+
+fn read_html_fragment(data: &IDataObject) -> Option<String> {
+    let format_name: Vec<u16> = "HTML Format\0".encode_utf16().collect();
+    let format = unsafe { RegisterClipboardFormatW(windows::core::PCWSTR(format_name.as_ptr())) };
+    if format == 0 {
+        return None;
+    }
+    let text = read_format(data, format as u16, false)?;
+    first_http_url(&text)
+}
+
+fn format_etc(format: u16) -> FORMATETC {
+    FORMATETC {
+        cfFormat: format,
+        ptd: std::ptr::null_mut(),
+        dwAspect: DVASPECT_CONTENT.0,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL.0 as u32,
+    }
+}
+
+fn read_format(data: &IDataObject, format: u16, is_unicode: bool) -> Option<String> {
+    unsafe {
+        let medium: STGMEDIUM = data.GetData(&format_etc(format)).ok()?;
+        if medium.tymed != TYMED_HGLOBAL.0 as u32 {
+            return None;
+        }
+
+        let hglobal: HGLOBAL = medium.u.hGlobal;
+        let ptr = GlobalLock(hglobal);
+        if ptr.is_null() {
+            return None;
+        }
+        let len = GlobalSize(hglobal);
+
+        let text = if is_unicode {
+            let slice = std::slice::from_raw_parts(ptr as *const u16, len / 2);
+            let end = slice.iter().position(|&c| c == 0).unwrap_or(slice.len());
+            String::from_utf16_lossy(&slice[..end])
+        } else {
+            let slice = std::slice::from_raw_parts(ptr as *const u8, len);
+            String::from_utf8_lossy(slice).into_owned()
+        };
+
+        GlobalUnlock(hglobal);
+        Some(text)
+    }
+}
+
+/// Kept for parity with the other platform backends; real extraction now
+/// happens through [`register`]'s drop target rather than a poll here.
+pub fn get_drag_url() -> Option<String> {
+    None
+}
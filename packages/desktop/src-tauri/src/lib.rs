@@ -1,81 +1,57 @@
+mod drag_source;
+mod portal_shortcut;
 mod server;
+mod window_state;
+
+use tauri::{Emitter, Listener, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Origins the app webview scheme resolves to, depending on platform.
+/// Neither variant embeds a remote host, so both are safe to trust alongside
+/// the local server origin.
+#[cfg(target_os = "windows")]
+const APP_ORIGIN: &str = "https://tauri.localhost";
+#[cfg(not(target_os = "windows"))]
+const APP_ORIGIN: &str = "tauri://localhost";
+
+/// Checks that `window` is currently showing a trusted origin before letting
+/// it invoke a privileged command. Blocks remote pages the window has been
+/// navigated to (a hijacked link, a redirected auth flow) from reaching IPC
+/// commands that were only ever meant for the app's own windows. Only the
+/// window's top-level URL is checked, so a frame embedded in a trusted page
+/// is not told apart from the page itself.
+fn require_trusted_origin(
+    window: &WebviewWindow,
+    server_state: &tauri::State<server::ServerState>,
+) -> Result<(), String> {
+    let url = window
+        .url()
+        .map_err(|e| format!("Failed to resolve window URL: {}", e))?;
+    // `Url::origin` is opaque ("null") for non-special schemes such as
+    // `tauri://`, so rebuild `scheme://host` for those.
+    let origin = match url.origin() {
+        url::Origin::Opaque(_) => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+        origin => origin.ascii_serialization(),
+    };
 
-use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
-use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
-
-#[cfg(target_os = "macos")]
-mod macos_pasteboard {
-    use cocoa::base::{id, nil};
-    use cocoa::foundation::NSString;
-    use objc::{class, msg_send, sel, sel_impl};
-
-    pub fn get_drag_url() -> Option<String> {
-        unsafe {
-            // Get the drag pasteboard
-            let pasteboard: id = msg_send![class!(NSPasteboard), pasteboardWithName: NSString::alloc(nil).init_str("Apple CFPasteboard drag")];
-
-            if pasteboard == nil {
-                // Try general pasteboard as fallback
-                let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
-                return read_url_from_pasteboard(pasteboard);
-            }
-
-            read_url_from_pasteboard(pasteboard)
-        }
-    }
-
-    unsafe fn read_url_from_pasteboard(pasteboard: id) -> Option<String> {
-        if pasteboard == nil {
-            return None;
-        }
-
-        // Try to read URL type
-        let url_type = NSString::alloc(nil).init_str("public.url");
-        let url_string_type = NSString::alloc(nil).init_str("public.url-name");
-        let string_type = NSString::alloc(nil).init_str("public.utf8-plain-text");
-
-        // Try URL first
-        let content: id = msg_send![pasteboard, stringForType: url_type];
-        if content != nil {
-            let rust_string = nsstring_to_rust(content);
-            if rust_string.starts_with("http://") || rust_string.starts_with("https://") {
-                return Some(rust_string);
-            }
-        }
-
-        // Try plain text (URLs are often stored as text)
-        let content: id = msg_send![pasteboard, stringForType: string_type];
-        if content != nil {
-            let rust_string = nsstring_to_rust(content);
-            if rust_string.starts_with("http://") || rust_string.starts_with("https://") {
-                return Some(rust_string);
-            }
-        }
-
-        None
-    }
-
-    unsafe fn nsstring_to_rust(nsstring: id) -> String {
-        let cstr: *const i8 = msg_send![nsstring, UTF8String];
-        if cstr.is_null() {
-            return String::new();
-        }
-        std::ffi::CStr::from_ptr(cstr)
-            .to_string_lossy()
-            .into_owned()
-    }
-}
-
-#[cfg(not(target_os = "macos"))]
-mod macos_pasteboard {
-    pub fn get_drag_url() -> Option<String> {
-        None
+    if origin == server_state.origin() || origin == APP_ORIGIN {
+        Ok(())
+    } else {
+        log::warn!(
+            "Blocked IPC invoke from untrusted origin: {} (window {:?})",
+            origin,
+            window.label()
+        );
+        Err(format!("Untrusted origin: {}", origin))
     }
 }
 
 #[tauri::command]
-fn get_server_port(state: tauri::State<server::ServerState>) -> u16 {
-    state.port()
+fn get_server_port(
+    window: WebviewWindow,
+    state: tauri::State<server::ServerState>,
+) -> Result<u16, String> {
+    require_trusted_origin(&window, &state)?;
+    Ok(state.port())
 }
 
 #[derive(Clone, serde::Serialize)]
@@ -87,9 +63,13 @@ struct PortalDropPayload {
 #[tauri::command]
 async fn add_url_from_portal(
     app: tauri::AppHandle,
+    window: WebviewWindow,
+    state: tauri::State<'_, server::ServerState>,
     url: String,
     collection_slug: Option<String>,
 ) -> Result<(), String> {
+    require_trusted_origin(&window, &state)?;
+
     log::info!(
         "URL dropped in portal: {} -> {:?}",
         url,
@@ -115,6 +95,34 @@ async fn add_url_from_portal(
     Ok(())
 }
 
+#[tauri::command]
+fn set_portal_visible_on_all_workspaces(
+    app: tauri::AppHandle,
+    window: WebviewWindow,
+    state: tauri::State<server::ServerState>,
+    visible_on_all_workspaces: bool,
+) -> Result<(), String> {
+    require_trusted_origin(&window, &state)?;
+
+    let portal = app
+        .get_webview_window("portal")
+        .ok_or_else(|| "Portal window not found".to_string())?;
+    portal
+        .set_visible_on_all_workspaces(visible_on_all_workspaces)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_portal_shortcut(
+    app: tauri::AppHandle,
+    window: WebviewWindow,
+    state: tauri::State<server::ServerState>,
+    accelerator: String,
+) -> Result<(), String> {
+    require_trusted_origin(&window, &state)?;
+    portal_shortcut::set_shortcut(&app, &accelerator)
+}
+
 fn create_portal_window(app: &tauri::AppHandle) -> tauri::Result<()> {
     // Get main window position to spawn portal nearby
     let position = if let Some(main_window) = app.get_webview_window("main") {
@@ -123,8 +131,6 @@ fn create_portal_window(app: &tauri::AppHandle) -> tauri::Result<()> {
         None
     };
 
-    // TODO: Load saved position from store (multi-monitor aware)
-    // For now, use default size and position near main window
     let default_width = 500.0;
     let default_height = 600.0;
 
@@ -141,15 +147,38 @@ fn create_portal_window(app: &tauri::AppHandle) -> tauri::Result<()> {
     .always_on_top(true)
     .visible(false)
     .decorations(false)            // Frameless - no native title bar
-    .skip_taskbar(true);
-
-    // Position near main window if available
-    if let Some(pos) = position {
+    .skip_taskbar(true)
+    .visible_on_all_workspaces(true); // Follow the user across macOS Spaces
+
+    // Prefer a saved position/size for the monitor under the cursor (or the
+    // primary monitor), clamped into that monitor, falling back to "near
+    // main window" when nothing is saved for that monitor yet.
+    let saved = app
+        .try_state::<window_state::WindowStateStore>()
+        .and_then(|store| window_state::restore_window_geometry(app, &store));
+
+    if let Some(geometry) = &saved {
+        builder = builder
+            .inner_size(geometry.width as f64, geometry.height as f64)
+            .position(geometry.x as f64, geometry.y as f64);
+    } else if let Some(pos) = position {
         builder = builder.position((pos.x + 50) as f64, (pos.y + 50) as f64);
     }
 
     let portal = builder.build()?;
 
+    // The builder's size/position setters take logical units, while the
+    // saved geometry is in physical pixels; re-apply it as physical so it
+    // lands where it was saved on scaled (HiDPI) displays.
+    if let Some(geometry) = &saved {
+        let _ = portal.set_size(tauri::PhysicalSize::new(geometry.width, geometry.height));
+        let _ = portal.set_position(tauri::PhysicalPosition::new(geometry.x, geometry.y));
+    }
+
+    if let Err(e) = drag_source::init_window(&portal) {
+        log::warn!("Failed to hook up drag source for portal window: {}", e);
+    }
+
     log::info!("Portal window created: {:?}", portal.label());
     Ok(())
 }
@@ -201,11 +230,26 @@ pub fn run() {
                 // Intercept close to hide instead of destroy
                 if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                     api.prevent_close();
+                    if let Some(store) = window.app_handle().try_state::<window_state::WindowStateStore>() {
+                        window_state::persist_window_geometry(window, &store);
+                    }
                     let _ = window.hide();
                     log::info!("Portal close requested - hiding instead");
                     return;
                 }
 
+                if matches!(
+                    event,
+                    tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
+                ) {
+                    // Moved/Resized fire continuously through a drag gesture;
+                    // debounce the disk write so it doesn't stutter the
+                    // window while the user is still dragging it.
+                    if let Some(store) = window.app_handle().try_state::<window_state::WindowStateStore>() {
+                        window_state::persist_window_geometry_debounced(window, &store);
+                    }
+                }
+
                 if let tauri::WindowEvent::DragDrop(drag_event) = event {
                     match drag_event {
                         tauri::DragDropEvent::Drop { paths, position: _ } => {
@@ -213,7 +257,7 @@ pub fn run() {
 
                             // If paths is empty, try to get URL from drag pasteboard
                             let url = if paths.is_empty() {
-                                macos_pasteboard::get_drag_url()
+                                drag_source::get_drag_url()
                             } else {
                                 // Check if any path is a URL
                                 paths.iter()
@@ -252,6 +296,9 @@ pub fn run() {
             // Store server state for later access
             app.manage(server_state);
 
+            // Load persisted portal window geometry (per-monitor)
+            app.manage(window_state::WindowStateStore::load(&handle));
+
             // Navigate to the local server once ready
             let main_window = app.get_webview_window("main")
                 .expect("main window not found");
@@ -270,17 +317,45 @@ pub fn run() {
                 }
             });
 
+            // Re-navigate to the server once it's respawned by the supervisor
+            let restarted_handle = handle.clone();
+            app.listen("server-restarted", move |event| {
+                let Ok(new_port) = serde_json::from_str::<u16>(event.payload()) else {
+                    return;
+                };
+                if let Some(main_window) = restarted_handle.get_webview_window("main") {
+                    if let Ok(url) = url::Url::parse(&format!("http://localhost:{}", new_port)) {
+                        log::info!("Server restarted on port {}, re-navigating main window", new_port);
+                        let _ = main_window.navigate(url);
+                    }
+                }
+            });
+
+            // Surface a crash-looping server to the user instead of leaving
+            // them stuck on a dead page with no explanation
+            let restart_failed_handle = handle.clone();
+            app.listen("server-restart-failed", move |_event| {
+                log::error!("Next.js server crash-looped past the retry limit");
+                if let Some(main_window) = restart_failed_handle.get_webview_window("main") {
+                    let _ = main_window.emit("server-unavailable", ());
+                }
+            });
+
             // Create the portal window (hidden by default)
             create_portal_window(app.handle())?;
 
-            // Register global shortcut: Cmd+Shift+P (macOS)
-            let shortcut = Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyP);
-            app.global_shortcut().register(shortcut)?;
-            log::info!("Registered global shortcut: Cmd+Shift+P");
+            // Register the portal toggle shortcut (persisted user choice, or
+            // the platform default if none was saved yet)
+            portal_shortcut::init(&handle)?;
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![get_server_port, add_url_from_portal])
+        .invoke_handler(tauri::generate_handler![
+            get_server_port,
+            add_url_from_portal,
+            set_portal_visible_on_all_workspaces,
+            set_portal_shortcut
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
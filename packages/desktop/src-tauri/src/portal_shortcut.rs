@@ -0,0 +1,245 @@
+use std::fs;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
+
+const SETTINGS_FILE: &str = "portal-shortcut.json";
+
+#[cfg(target_os = "macos")]
+const DEFAULT_ACCELERATOR: &str = "Cmd+Shift+P";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_ACCELERATOR: &str = "Ctrl+Shift+P";
+
+#[derive(Serialize, Deserialize)]
+struct PortalShortcutSettings {
+    accelerator: String,
+}
+
+/// Tracks the portal toggle's currently registered accelerator so it can be
+/// unregistered again when the user picks a new one, and persists the
+/// choice so it's re-applied on next launch.
+pub struct PortalShortcutStore {
+    path: std::path::PathBuf,
+    current: Mutex<(String, Shortcut)>,
+}
+
+impl PortalShortcutStore {
+    pub fn current_accelerator(&self) -> String {
+        self.current.lock().unwrap().0.clone()
+    }
+
+    pub fn current_shortcut(&self) -> Shortcut {
+        self.current.lock().unwrap().1
+    }
+
+    fn set_current(&self, accelerator: &str, shortcut: Shortcut) {
+        *self.current.lock().unwrap() = (accelerator.to_string(), shortcut);
+    }
+
+    fn persist(&self) {
+        if let Some(parent) = self.path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                log::warn!("Failed to create portal shortcut settings dir: {}", e);
+                return;
+            }
+        }
+        let settings = PortalShortcutSettings {
+            accelerator: self.current_accelerator(),
+        };
+        match serde_json::to_string_pretty(&settings) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&self.path, json) {
+                    log::warn!("Failed to persist portal shortcut: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize portal shortcut settings: {}", e),
+        }
+    }
+}
+
+fn settings_path(app: &AppHandle) -> std::path::PathBuf {
+    app.path()
+        .app_config_dir()
+        .unwrap_or_default()
+        .join(SETTINGS_FILE)
+}
+
+fn saved_accelerator(app: &AppHandle) -> String {
+    fs::read_to_string(settings_path(app))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<PortalShortcutSettings>(&contents).ok())
+        .map(|settings| settings.accelerator)
+        .unwrap_or_else(|| DEFAULT_ACCELERATOR.to_string())
+}
+
+/// Parses an accelerator string like `"Cmd+Shift+P"` into the modifiers and
+/// key code `tauri_plugin_global_shortcut` expects.
+pub fn parse_accelerator(accelerator: &str) -> Result<(Option<Modifiers>, Code), String> {
+    let mut modifiers = Modifiers::empty();
+    let mut code = None;
+
+    for token in accelerator.split('+').map(str::trim).filter(|t| !t.is_empty()) {
+        match token.to_ascii_lowercase().as_str() {
+            "cmd" | "command" | "super" | "win" | "windows" | "meta" => modifiers |= Modifiers::SUPER,
+            "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
+            "shift" => modifiers |= Modifiers::SHIFT,
+            "alt" | "option" => modifiers |= Modifiers::ALT,
+            key => code = Some(parse_code(key)?),
+        }
+    }
+
+    let code = code.ok_or_else(|| format!("No key found in accelerator: {}", accelerator))?;
+    let modifiers = if modifiers.is_empty() { None } else { Some(modifiers) };
+    Ok((modifiers, code))
+}
+
+fn parse_code(key: &str) -> Result<Code, String> {
+    if let Some(rest) = key.strip_prefix('f') {
+        if let Ok(n) = rest.parse::<u8>() {
+            return function_key(n).ok_or_else(|| format!("Unsupported function key: {}", key));
+        }
+    }
+
+    if key.len() == 1 {
+        let ch = key.chars().next().unwrap();
+        if ch.is_ascii_alphabetic() {
+            return Ok(letter_key(ch.to_ascii_uppercase()));
+        }
+        if ch.is_ascii_digit() {
+            return Ok(digit_key(ch));
+        }
+    }
+
+    match key {
+        "space" => Ok(Code::Space),
+        "enter" | "return" => Ok(Code::Enter),
+        "tab" => Ok(Code::Tab),
+        "escape" | "esc" => Ok(Code::Escape),
+        _ => Err(format!("Unsupported key: {}", key)),
+    }
+}
+
+fn letter_key(ch: char) -> Code {
+    match ch {
+        'A' => Code::KeyA, 'B' => Code::KeyB, 'C' => Code::KeyC, 'D' => Code::KeyD,
+        'E' => Code::KeyE, 'F' => Code::KeyF, 'G' => Code::KeyG, 'H' => Code::KeyH,
+        'I' => Code::KeyI, 'J' => Code::KeyJ, 'K' => Code::KeyK, 'L' => Code::KeyL,
+        'M' => Code::KeyM, 'N' => Code::KeyN, 'O' => Code::KeyO, 'P' => Code::KeyP,
+        'Q' => Code::KeyQ, 'R' => Code::KeyR, 'S' => Code::KeyS, 'T' => Code::KeyT,
+        'U' => Code::KeyU, 'V' => Code::KeyV, 'W' => Code::KeyW, 'X' => Code::KeyX,
+        'Y' => Code::KeyY, _ => Code::KeyZ,
+    }
+}
+
+fn digit_key(ch: char) -> Code {
+    match ch {
+        '0' => Code::Digit0, '1' => Code::Digit1, '2' => Code::Digit2, '3' => Code::Digit3,
+        '4' => Code::Digit4, '5' => Code::Digit5, '6' => Code::Digit6, '7' => Code::Digit7,
+        '8' => Code::Digit8, _ => Code::Digit9,
+    }
+}
+
+fn function_key(n: u8) -> Option<Code> {
+    Some(match n {
+        1 => Code::F1, 2 => Code::F2, 3 => Code::F3, 4 => Code::F4,
+        5 => Code::F5, 6 => Code::F6, 7 => Code::F7, 8 => Code::F8,
+        9 => Code::F9, 10 => Code::F10, 11 => Code::F11, 12 => Code::F12,
+        _ => return None,
+    })
+}
+
+/// Registers `accelerator` as the portal toggle shortcut, unregistering
+/// whatever was previously registered first. Persists the new accelerator
+/// on success; leaves the previous one in place (and returns `Err`) if the
+/// combo is already claimed by the OS or another app.
+pub fn set_shortcut(app: &AppHandle, accelerator: &str) -> Result<(), String> {
+    let (modifiers, code) = parse_accelerator(accelerator)?;
+    let shortcut = Shortcut::new(modifiers, code);
+
+    let store = app
+        .try_state::<PortalShortcutStore>()
+        .ok_or_else(|| "Portal shortcut store not initialized".to_string())?;
+
+    // An empty accelerator means startup registered nothing; the stored
+    // shortcut is then only a placeholder and is never (re)registered.
+    let previous = (!store.current_accelerator().is_empty()).then(|| store.current_shortcut());
+    if let Some(previous) = previous {
+        let _ = app.global_shortcut().unregister(previous);
+    }
+
+    if let Err(e) = app.global_shortcut().register(shortcut) {
+        // Restore the previous shortcut so the user isn't left with none.
+        if let Some(previous) = previous {
+            let _ = app.global_shortcut().register(previous);
+        }
+        return Err(format!("Failed to register shortcut {}: {}", accelerator, e));
+    }
+
+    store.set_current(accelerator, shortcut);
+    store.persist();
+    log::info!("Registered portal shortcut: {}", accelerator);
+    Ok(())
+}
+
+/// Parses and registers `accelerator`, returning the parsed shortcut on
+/// success so the caller can remember it.
+fn try_register(app: &AppHandle, accelerator: &str) -> Result<Shortcut, String> {
+    let (modifiers, code) = parse_accelerator(accelerator)?;
+    let shortcut = Shortcut::new(modifiers, code);
+    app.global_shortcut()
+        .register(shortcut)
+        .map_err(|e| e.to_string())?;
+    Ok(shortcut)
+}
+
+/// Loads the persisted accelerator (or the platform default) and registers
+/// it. Startup must never panic over this: if the persisted accelerator is
+/// already claimed by the OS or another app, we fall back to the hardcoded
+/// default, and if even that fails we log the error and continue with no
+/// shortcut bound rather than bubbling the error out of `setup()` (which
+/// `run()` turns into a hard panic).
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let saved = saved_accelerator(app);
+
+    let registered = match try_register(app, &saved) {
+        Ok(shortcut) => Some((saved, shortcut)),
+        Err(e) => {
+            log::warn!("Failed to register portal shortcut {}: {}", saved, e);
+            if saved == DEFAULT_ACCELERATOR {
+                None
+            } else {
+                match try_register(app, DEFAULT_ACCELERATOR) {
+                    Ok(shortcut) => Some((DEFAULT_ACCELERATOR.to_string(), shortcut)),
+                    Err(e) => {
+                        log::error!(
+                            "Failed to register default portal shortcut {}: {}",
+                            DEFAULT_ACCELERATOR,
+                            e
+                        );
+                        None
+                    }
+                }
+            }
+        }
+    };
+
+    let current = match registered {
+        Some((accelerator, shortcut)) => {
+            log::info!("Registered portal shortcut: {}", accelerator);
+            (accelerator, shortcut)
+        }
+        None => {
+            log::error!("Starting with no portal shortcut registered; use the settings UI to pick one");
+            (String::new(), Shortcut::new(None, Code::KeyP))
+        }
+    };
+
+    app.manage(PortalShortcutStore {
+        path: settings_path(app),
+        current: Mutex::new(current),
+    });
+
+    Ok(())
+}
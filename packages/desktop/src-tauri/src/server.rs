@@ -1,19 +1,34 @@
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::{AppHandle, Manager};
+use tauri::async_runtime::Receiver;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tauri_plugin_shell::ShellExt;
-use tauri_plugin_shell::process::CommandChild;
 
-/// Holds the server state including the spawned process and port
+/// Cap on consecutive failed restart attempts before we give up and surface
+/// an error instead of crash-looping the sidecar forever.
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Holds the server state including the spawned process and port. `port`
+/// and `child` are shared with the supervisor task so a restart is visible
+/// to every command that reads `ServerState` from Tauri's managed state.
 pub struct ServerState {
-    port: u16,
+    port: Arc<AtomicU16>,
     #[allow(dead_code)]
-    child: Arc<CommandChild>,
+    child: Arc<Mutex<CommandChild>>,
 }
 
 impl ServerState {
     pub fn port(&self) -> u16 {
-        self.port
+        self.port.load(Ordering::SeqCst)
+    }
+
+    /// The origin of the local Next.js server (e.g. `http://localhost:PORT`),
+    /// used as one of the trusted origins allowed to invoke IPC commands.
+    /// Recomputed from the live port so it stays correct across restarts.
+    pub fn origin(&self) -> String {
+        format!("http://localhost:{}", self.port())
     }
 }
 
@@ -22,9 +37,13 @@ fn find_available_port() -> Result<u16, Box<dyn std::error::Error>> {
     portpicker::pick_unused_port().ok_or_else(|| "No available port found".into())
 }
 
-/// Start the Next.js standalone server as a sidecar process
-pub fn start_server(app: &AppHandle) -> Result<ServerState, Box<dyn std::error::Error>> {
-    let port = find_available_port()?;
+/// Spawns the Node.js sidecar on `port`, returning its event stream and
+/// process handle. Used both for the initial start and for supervisor
+/// restarts.
+fn spawn_server_process(
+    app: &AppHandle,
+    port: u16,
+) -> Result<(Receiver<CommandEvent>, CommandChild), Box<dyn std::error::Error>> {
     log::info!("Starting Next.js server on port {}", port);
 
     // Get the path to the standalone server
@@ -47,16 +66,111 @@ pub fn start_server(app: &AppHandle) -> Result<ServerState, Box<dyn std::error::
         .env("PORT", port.to_string())
         .env("HOSTNAME", "localhost".to_string());
 
-    let (_, child) = command
+    let (rx, child) = command
         .spawn()
         .map_err(|e| format!("Failed to spawn server: {}", e))?;
 
     log::info!("Next.js server process spawned");
+    Ok((rx, child))
+}
+
+/// Start the Next.js standalone server as a sidecar process, supervised so
+/// an unexpected exit triggers an automatic respawn.
+pub fn start_server(app: &AppHandle) -> Result<ServerState, Box<dyn std::error::Error>> {
+    let port = find_available_port()?;
+    let (rx, child) = spawn_server_process(app, port)?;
 
-    Ok(ServerState {
-        port,
-        child: Arc::new(child),
-    })
+    let port = Arc::new(AtomicU16::new(port));
+    let child = Arc::new(Mutex::new(child));
+
+    spawn_supervisor(app.clone(), rx, port.clone(), child.clone());
+
+    Ok(ServerState { port, child })
+}
+
+/// Consumes the sidecar's event stream, logging its stdout/stderr through
+/// `log`, and on an unexpected termination picks a fresh port and respawns
+/// it with exponential backoff, emitting `server-restarted` (new port) on
+/// success or `server-restart-failed` once `MAX_RESTART_ATTEMPTS` is hit.
+fn spawn_supervisor(
+    app: AppHandle,
+    mut rx: Receiver<CommandEvent>,
+    port: Arc<AtomicU16>,
+    child: Arc<Mutex<CommandChild>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut attempt = 0u32;
+        // A server that stays up this long before exiting isn't crash-looping,
+        // so its exit starts the restart count afresh.
+        let stable_uptime = Duration::from_secs(60);
+
+        loop {
+            let running_since = std::time::Instant::now();
+            while let Some(event) = rx.recv().await {
+                match event {
+                    CommandEvent::Stdout(line) => {
+                        log::info!("[server] {}", String::from_utf8_lossy(&line));
+                    }
+                    CommandEvent::Stderr(line) => {
+                        log::warn!("[server] {}", String::from_utf8_lossy(&line));
+                    }
+                    CommandEvent::Error(e) => {
+                        log::error!("Next.js server sidecar error: {}", e);
+                    }
+                    CommandEvent::Terminated(payload) => {
+                        log::error!("Next.js server exited unexpectedly: {:?}", payload);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            if running_since.elapsed() >= stable_uptime {
+                attempt = 0;
+            }
+            attempt += 1;
+            if attempt > MAX_RESTART_ATTEMPTS {
+                log::error!(
+                    "Next.js server crash-looped {} times; giving up",
+                    attempt - 1
+                );
+                let _ = app.emit("server-restart-failed", ());
+                return;
+            }
+
+            let backoff = Duration::from_secs(2u64.pow(attempt.min(5)));
+            log::warn!(
+                "Restarting Next.js server in {:?} (attempt {}/{})",
+                backoff,
+                attempt,
+                MAX_RESTART_ATTEMPTS
+            );
+            tokio::time::sleep(backoff).await;
+
+            let new_port = match find_available_port() {
+                Ok(p) => p,
+                Err(e) => {
+                    log::error!("Failed to find a port for server restart: {}", e);
+                    continue;
+                }
+            };
+
+            match spawn_server_process(&app, new_port) {
+                Ok((new_rx, new_child)) => {
+                    *child.lock().unwrap() = new_child;
+                    port.store(new_port, Ordering::SeqCst);
+                    rx = new_rx;
+
+                    wait_for_server(new_port).await;
+                    log::info!("Next.js server restarted on port {}", new_port);
+                    let _ = app.emit("server-restarted", new_port);
+                }
+                Err(e) => {
+                    log::error!("Failed to respawn Next.js server: {}", e);
+                }
+            }
+        }
+    });
 }
 
 /// Wait for the server to be ready by polling the health endpoint
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, Window};
+
+const STATE_FILE: &str = "portal-window-state.json";
+
+/// How long to wait after the last move/resize tick before writing to disk,
+/// so a drag or resize gesture (which fires many events per second) doesn't
+/// block the event loop with synchronous file I/O on every tick.
+const FLUSH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Saved geometry for a window on a particular monitor, keyed by monitor
+/// name in [`WindowStateStore`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub monitor_id: String,
+}
+
+/// Per-monitor window geometry, persisted to a JSON file in the app config
+/// dir so the portal reopens where the user left it on each display.
+///
+/// Cloning is cheap (it's just the shared `Arc`s below), which lets
+/// [`WindowStateStore::save_debounced`] hand a copy to a delayed background
+/// task without needing the whole store wrapped in an `Arc`.
+#[derive(Clone)]
+pub struct WindowStateStore {
+    path: Arc<PathBuf>,
+    entries: Arc<Mutex<HashMap<String, WindowGeometry>>>,
+    generation: Arc<AtomicU64>,
+}
+
+impl WindowStateStore {
+    /// Loads saved geometry from disk, starting empty if none exists yet.
+    pub fn load(app: &AppHandle) -> Self {
+        let path = app
+            .path()
+            .app_config_dir()
+            .unwrap_or_default()
+            .join(STATE_FILE);
+
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            path: Arc::new(path),
+            entries: Arc::new(Mutex::new(entries)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns the saved geometry for `monitor_id`, if any.
+    pub fn geometry_for_monitor(&self, monitor_id: &str) -> Option<WindowGeometry> {
+        self.entries.lock().unwrap().get(monitor_id).cloned()
+    }
+
+    /// Records `geometry` for its monitor and flushes the store to disk
+    /// immediately. Use this for one-off saves (e.g. on window close).
+    pub fn save(&self, geometry: WindowGeometry) {
+        self.update(geometry);
+        self.flush();
+    }
+
+    /// Records `geometry` for its monitor right away (cheap, in-memory), but
+    /// defers the disk write for [`FLUSH_DEBOUNCE`], skipping it entirely if
+    /// another `save_debounced` call comes in first. Use this for high-frequency updates
+    /// like window move/resize events.
+    pub fn save_debounced(&self, geometry: WindowGeometry) {
+        self.update(geometry);
+
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let store = self.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(FLUSH_DEBOUNCE).await;
+            // Skip the write if a newer save superseded this one.
+            if store.generation.load(Ordering::SeqCst) == generation {
+                store.flush();
+            }
+        });
+    }
+
+    fn update(&self, geometry: WindowGeometry) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(geometry.monitor_id.clone(), geometry);
+    }
+
+    fn flush(&self) {
+        let entries = self.entries.lock().unwrap();
+        if let Some(parent) = self.path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                log::warn!("Failed to create window state dir: {}", e);
+                return;
+            }
+        }
+        match serde_json::to_string_pretty(&*entries) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&*self.path, json) {
+                    log::warn!("Failed to persist window state: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize window state: {}", e),
+        }
+    }
+}
+
+/// Captures `window`'s current position/size and saves it (debounced) under
+/// the monitor it's currently on. Ignored if the monitor can't be resolved.
+pub fn persist_window_geometry_debounced(window: &Window, store: &WindowStateStore) {
+    if let Some(geometry) = current_geometry(window) {
+        store.save_debounced(geometry);
+    }
+}
+
+/// Captures `window`'s current position/size and saves it immediately.
+/// Intended for one-off saves such as on window close.
+pub fn persist_window_geometry(window: &Window, store: &WindowStateStore) {
+    if let Some(geometry) = current_geometry(window) {
+        store.save(geometry);
+    }
+}
+
+fn current_geometry(window: &Window) -> Option<WindowGeometry> {
+    let monitor = window.current_monitor().ok().flatten()?;
+    let position = window.outer_position().ok()?;
+    let size = window.outer_size().ok()?;
+
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        monitor_id: monitor_id(&monitor),
+    })
+}
+
+/// Returns the saved geometry for the monitor under the cursor (or the
+/// primary monitor as fallback), clamped into that monitor's current bounds,
+/// if one was saved for it.
+pub fn restore_window_geometry(app: &AppHandle, store: &WindowStateStore) -> Option<WindowGeometry> {
+    let monitor = cursor_monitor(app).or_else(|| primary_monitor(app))?;
+    let saved = store.geometry_for_monitor(&monitor_id(&monitor))?;
+    Some(clamp_to_work_area(saved, &monitor))
+}
+
+fn monitor_id(monitor: &Monitor) -> String {
+    monitor
+        .name()
+        .cloned()
+        .unwrap_or_else(|| format!("{:?}", monitor.position()))
+}
+
+fn cursor_monitor(app: &AppHandle) -> Option<Monitor> {
+    let main_window = app.get_webview_window("main")?;
+    let cursor = main_window.cursor_position().ok()?;
+    main_window
+        .available_monitors()
+        .ok()?
+        .into_iter()
+        .find(|m| monitor_contains(m, cursor.x as i32, cursor.y as i32))
+}
+
+fn primary_monitor(app: &AppHandle) -> Option<Monitor> {
+    app.primary_monitor().ok().flatten()
+}
+
+fn monitor_contains(monitor: &Monitor, x: i32, y: i32) -> bool {
+    let pos = monitor.position();
+    let size = monitor.size();
+    x >= pos.x && x < pos.x + size.width as i32 && y >= pos.y && y < pos.y + size.height as i32
+}
+
+/// Clamps a saved rect into `monitor`'s bounds, in case the saved geometry
+/// no longer fits (e.g. the monitor's resolution changed, or it was
+/// unplugged and replaced by a smaller one). The bounds are the monitor's
+/// full position and size; space taken by a taskbar, dock or menu bar is
+/// not excluded.
+fn clamp_to_work_area(mut geometry: WindowGeometry, monitor: &Monitor) -> WindowGeometry {
+    let work_area_position: PhysicalPosition<i32> = monitor.position().to_owned();
+    let work_area_size: PhysicalSize<u32> = monitor.size().to_owned();
+
+    geometry.width = geometry.width.min(work_area_size.width);
+    geometry.height = geometry.height.min(work_area_size.height);
+
+    let max_x = work_area_position.x + work_area_size.width as i32 - geometry.width as i32;
+    let max_y = work_area_position.y + work_area_size.height as i32 - geometry.height as i32;
+
+    geometry.x = geometry.x.clamp(work_area_position.x, max_x.max(work_area_position.x));
+    geometry.y = geometry.y.clamp(work_area_position.y, max_y.max(work_area_position.y));
+
+    geometry
+}